@@ -3,74 +3,167 @@
  * Support analysis with memory safety
  */
 
-fn generate_primes(limit: usize) -> Vec<usize> {
-    let mut is_prime = vec![true; limit + 1];
-    if limit >= 2 {
-        is_prime[0] = false;
-        is_prime[1] = false;
-    }
-    
-    let mut primes = Vec::new();
-    for i in 2..=limit {
-        if is_prime[i] {
-            primes.push(i);
-            let mut j = i * i;
-            while j <= limit {
-                is_prime[j] = false;
-                j += i;
+/// Bit-packed sieve of Eratosthenes over the odd numbers `3..=limit`.
+///
+/// Odd number `n` is tracked at index `i = (n - 3) / 2`, and the composite
+/// flag for index `i` lives in word `i >> 5` at bit `i & 31`. This keeps
+/// memory at roughly `limit / 16` bytes instead of one byte per integer,
+/// so `limit` can reach into the hundreds of millions.
+struct OddSieve {
+    limit: usize,
+    words: Vec<u32>,
+}
+
+impl OddSieve {
+    fn new(limit: usize) -> Self {
+        let ndxlmt = if limit >= 3 { (limit - 3) / 2 + 1 } else { 0 };
+        let mut words = vec![0u32; ndxlmt.div_ceil(32)];
+
+        let sqrt_lmt = (limit as f64).sqrt().ceil() as usize;
+        let base_ndxlmt = if sqrt_lmt >= 3 { (sqrt_lmt - 3) / 2 + 1 } else { 0 };
+
+        for ndx in 0..base_ndxlmt.min(ndxlmt) {
+            if Self::bit(&words, ndx) {
+                continue;
+            }
+            let p = 2 * ndx + 3;
+            let mut cullpos = (p * p - 3) / 2;
+            while cullpos < ndxlmt {
+                Self::set_bit(&mut words, cullpos);
+                cullpos += p;
             }
         }
+
+        OddSieve { limit, words }
+    }
+
+    fn bit(words: &[u32], ndx: usize) -> bool {
+        words[ndx >> 5] & (1 << (ndx & 31)) != 0
+    }
+
+    fn set_bit(words: &mut [u32], ndx: usize) {
+        words[ndx >> 5] |= 1 << (ndx & 31);
+    }
+
+    /// Yields every prime in `2..=limit`, starting with the special case 2.
+    fn primes_iter(&self) -> impl Iterator<Item = usize> + '_ {
+        let ndxlmt = if self.limit >= 3 { (self.limit - 3) / 2 + 1 } else { 0 };
+        let has_two = self.limit >= 2;
+        has_two
+            .then_some(2)
+            .into_iter()
+            .chain((0..ndxlmt).filter_map(move |ndx| {
+                if Self::bit(&self.words, ndx) {
+                    None
+                } else {
+                    Some(2 * ndx + 3)
+                }
+            }))
     }
-    primes
+}
+
+const SMALL_PRIMES: [usize; 5] = [2, 3, 5, 7, 11];
+
+/// Upper bound on the n-th prime (1-indexed) tight enough to sieve directly.
+///
+/// For `n >= 6` this uses `p_n <= n * (ln n + ln ln n)`; smaller `n` are
+/// covered by `SMALL_PRIMES` instead, since the bound isn't valid there.
+fn nth_prime_bound(n: usize) -> usize {
+    let n_f = n as f64;
+    let ln = n_f.ln();
+    let lnln = ln.ln();
+    (n_f * (ln + lnln)).ceil() as usize
+}
+
+/// Computes the n-th prime (1-indexed: `nth_prime(1) == 2`) without
+/// overshooting memory the way a hardcoded `max_prime` guess would.
+///
+/// `n` is 1-indexed, so `n == 0` is out of range; this fails fast with an
+/// explicit `assert!` instead of silently underflowing into an out-of-bounds
+/// index. This is the native/internal API — it still panics on `n == 0`, so
+/// callers across the WASM C-ABI boundary must not call it directly with
+/// unchecked input; `envr_nth_prime` guards `n == 0` itself before reaching here.
+fn nth_prime(n: usize) -> usize {
+    assert!(n >= 1, "nth_prime is 1-indexed; n must be >= 1, got {}", n);
+    if n < 6 {
+        return SMALL_PRIMES[n - 1];
+    }
+    let limit = nth_prime_bound(n);
+    OddSieve::new(limit)
+        .primes_iter()
+        .nth(n - 1)
+        .expect("nth_prime_bound should always cover the n-th prime")
 }
 
 struct SupportAnalyzerRust {
     max_prime: usize,
-    primes: Vec<usize>,
+    sieve: OddSieve,
+    /// Caps how many primes `primes_iter` yields; `None` means "all of them"
+    /// (the `new` path), `Some(n)` means "only the first `n`" (`with_nth_prime`).
+    prime_cap: Option<usize>,
 }
 
 impl SupportAnalyzerRust {
     fn new(max_prime: usize) -> Self {
-        let primes = generate_primes(max_prime);
-        SupportAnalyzerRust { max_prime, primes }
+        SupportAnalyzerRust {
+            max_prime,
+            sieve: OddSieve::new(max_prime),
+            prime_cap: None,
+        }
+    }
+
+    /// Builds an analyzer holding exactly the first `n` primes, sizing the
+    /// sieve from the analytic upper bound on `p_n` instead of a guessed
+    /// `max_prime` cutoff.
+    fn with_nth_prime(n: usize) -> Self {
+        assert!(n >= 1, "with_nth_prime is 1-indexed; n must be >= 1, got {}", n);
+        let limit = if n < 6 { SMALL_PRIMES[n - 1] } else { nth_prime_bound(n) };
+        let sieve = OddSieve::new(limit);
+        let max_prime = sieve
+            .primes_iter()
+            .take(n)
+            .last()
+            .expect("sieve limit should always cover the n-th prime");
+        SupportAnalyzerRust { max_prime, sieve, prime_cap: Some(n) }
+    }
+
+    /// Raw primes in the support, streamed lazily straight off the sieve's
+    /// cleared bits so `.take(k)` callers never pay for primes they don't
+    /// consume, and nothing is materialized up front.
+    fn primes_iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.sieve.primes_iter().take(self.prime_cap.unwrap_or(usize::MAX))
+    }
+
+    /// Support elements formatted as `(p)`, streamed lazily over `primes_iter`.
+    fn support_iter(&self) -> impl Iterator<Item = String> + '_ {
+        self.primes_iter().map(|p| format!("({})", p))
     }
-    
+
     fn compute_support(&self) -> Vec<String> {
-        self.primes.iter()
-            .map(|&p| format!("({})", p))
-            .collect()
+        self.support_iter().collect()
     }
-    
-    fn is_zariski_closed(&self, support: &[String]) -> bool {
-        if support.is_empty() {
-            return true;
-        }
-        
-        // In Spec(Z), closed sets are finite or whole space
-        if support.len() == self.primes.len() {
-            return false; // Infinite but not whole space
-        }
-        
-        // Finite sets are closed
-        support.len() < self.primes.len()
+
+    fn prime_count(&self) -> usize {
+        self.primes_iter().count()
     }
-    
+
+    #[cfg(not(target_arch = "wasm32"))]
     fn analyze(&self) {
-        let support = self.compute_support();
-        let closed = self.is_zariski_closed(&support);
-        
+        let count = self.prime_count();
+        let closed = is_zariski_closed_logic(count, count);
+
         println!("=== SLK8 Problem Analysis (Rust) ===");
         println!("Maximum prime considered: {}", self.max_prime);
-        println!("Support size: {}", support.len());
-        
+        println!("Support size: {}", count);
+
         print!("First 10 primes in support: ");
-        for i in 0..std::cmp::min(10, support.len()) {
-            print!("{} ", support[i]);
+        for s in self.support_iter().take(10) {
+            print!("{} ", s);
         }
         println!();
-        
+
         println!("Is Zariski closed? {}", if closed { "Yes" } else { "No" });
-        
+
         println!("\nMathematical Interpretation:");
         println!("Ring: ℤ (integers)");
         println!("Module: M = ℚ/ℤ");
@@ -80,7 +173,62 @@ impl SupportAnalyzerRust {
     }
 }
 
+/// Pure Zariski-closedness check, shared by `SupportAnalyzerRust::analyze`
+/// and the WASM C-ABI export: a support of `support_len` primes is closed
+/// exactly when it is empty or has fewer than `prime_count` elements (a
+/// finite set); one covering all `prime_count` primes stands for the
+/// infinite set of all primes, which is not the whole space, so not closed.
+fn is_zariski_closed_logic(support_len: usize, prime_count: usize) -> bool {
+    if support_len == 0 {
+        return true;
+    }
+
+    // In Spec(Z), closed sets are finite or whole space
+    if support_len == prime_count {
+        return false; // Infinite but not whole space
+    }
+
+    // Finite sets are closed
+    support_len < prime_count
+}
+
+/// Number of primes up to `max_prime`, without formatting them into `(p)` strings.
+fn support_size(max_prime: usize) -> usize {
+    OddSieve::new(max_prime).primes_iter().count()
+}
+
+/// C-ABI entry points for a `wasm32-unknown-unknown` build: pure functions
+/// over the sieve/support logic above, free of `println!`/stdout so they
+/// link without WASI.
+/// Returns `0` for `n == 0` instead of panicking: `nth_prime` asserts on
+/// out-of-range input, and a panic unwinding across this `extern "C"`
+/// boundary would abort/trap the wasm instance rather than return to JS.
+#[no_mangle]
+pub extern "C" fn envr_nth_prime(n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+    nth_prime(n)
+}
+
+#[no_mangle]
+pub extern "C" fn envr_support_size(max_prime: usize) -> usize {
+    support_size(max_prime)
+}
+
+#[no_mangle]
+pub extern "C" fn envr_is_zariski_closed(support_len: usize, prime_count: usize) -> u32 {
+    is_zariski_closed_logic(support_len, prime_count) as u32
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 fn main() {
     let analyzer = SupportAnalyzerRust::new(50);
     analyzer.analyze();
+
+    let first_ten = SupportAnalyzerRust::with_nth_prime(10);
+    println!(
+        "\nSupport of the first 10 primes: {:?}",
+        first_ten.compute_support()
+    );
 }